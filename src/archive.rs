@@ -0,0 +1,91 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::models::{Session, Window};
+
+/// Bump this whenever the archive's on-disk shape changes in a way that
+/// isn't backwards compatible with a plain `serde_json` deserialize.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Metadata {
+    pub tici_version: String,
+    pub hostname: String,
+    pub saved_at: u64,
+    pub origin_dir: String,
+}
+
+/// A full, self-describing save of one or more tmux sessions: sessions,
+/// windows and panes, plus enough metadata to tell saves apart and to
+/// evolve the format over time.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Archive {
+    pub version: u32,
+    pub metadata: Metadata,
+    pub sessions: Vec<Session>,
+}
+
+impl Archive {
+    /// Build an archive covering every session passed in (`tici save --all`
+    /// ends up with more than one; a plain `tici save` with exactly one).
+    pub fn new(sessions: Vec<Session>, origin_dir: &Path) -> Self {
+        Archive {
+            version: ARCHIVE_VERSION,
+            metadata: Metadata {
+                tici_version: env!("CARGO_PKG_VERSION").to_string(),
+                hostname: get_hostname(),
+                saved_at: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0),
+                origin_dir: origin_dir.to_string_lossy().to_string(),
+            },
+            sessions,
+        }
+    }
+
+    /// Build an archive for a single session's windows, e.g. from a plain
+    /// `tici save`.
+    pub fn single(session_name: &str, windows: Vec<Window>, origin_dir: &Path) -> Self {
+        Self::new(
+            vec![Session {
+                name: session_name.to_string(),
+                windows,
+            }],
+            origin_dir,
+        )
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize session archive")
+    }
+
+    pub fn from_json(content: &str) -> Result<Self> {
+        let archive: Self =
+            serde_json::from_str(content).context("Failed to parse session archive")?;
+
+        if archive.version > ARCHIVE_VERSION {
+            anyhow::bail!(
+                "Archive format version {} is newer than this tici understands (max {}); \
+                 upgrade tici to restore it",
+                archive.version,
+                ARCHIVE_VERSION
+            );
+        }
+
+        Ok(archive)
+    }
+}
+
+fn get_hostname() -> String {
+    Command::new("hostname")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hostname| hostname.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
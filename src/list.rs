@@ -0,0 +1,125 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::archive::Archive;
+use crate::tmux;
+
+/// Pull the session name out of a save filename, e.g.
+/// `session_0123456789abcdef_myproject.tmux` -> `myproject`. The hash is a
+/// fixed 16 hex characters with no underscores, so splitting on the first
+/// `_` cleanly separates it from a session name that may itself contain
+/// underscores.
+fn parse_save_filename(file_name: &str) -> Option<String> {
+    let stripped = file_name
+        .strip_prefix("session_")?
+        .strip_suffix(".tmux")?;
+    let (_hash, name) = stripped.split_once('_')?;
+    Some(name.to_string())
+}
+
+/// Best-effort metadata for a save file: `None` for a field means either the
+/// file is in the legacy `|`-delimited format (which never recorded it) or
+/// it couldn't be read.
+fn read_metadata(path: &Path) -> (Option<String>, Option<u64>) {
+    match fs::read_to_string(path).ok().and_then(|c| Archive::from_json(&c).ok()) {
+        Some(archive) => (
+            Some(archive.metadata.origin_dir),
+            Some(archive.metadata.saved_at),
+        ),
+        None => (None, None),
+    }
+}
+
+/// Sessions bundled into `all-sessions.tmux` by `tici save --all`. That file
+/// doesn't match the `session_<hash>_<name>.tmux` naming `parse_save_filename`
+/// expects, so it's invisible to the `read_dir` scan in `list_saved_sessions` — read it
+/// separately and surface its sessions the same way.
+fn read_all_sessions_archive(save_dir: &Path) -> Vec<(String, Option<String>, Option<u64>)> {
+    let path = save_dir.join("all-sessions.tmux");
+    let Some(archive) = fs::read_to_string(&path)
+        .ok()
+        .and_then(|c| Archive::from_json(&c).ok())
+    else {
+        return Vec::new();
+    };
+
+    let origin_dir = archive.metadata.origin_dir;
+    let saved_at = archive.metadata.saved_at;
+
+    archive
+        .sessions
+        .into_iter()
+        .map(|session| (session.name, Some(origin_dir.clone()), Some(saved_at)))
+        .collect()
+}
+
+fn format_relative(saved_at: u64) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(saved_at);
+    let diff = now.saturating_sub(saved_at);
+
+    match diff {
+        0..=59 => format!("{}s ago", diff),
+        60..=3599 => format!("{}m ago", diff / 60),
+        3600..=86399 => format!("{}h ago", diff / 3600),
+        _ => format!("{}d ago", diff / 86400),
+    }
+}
+
+/// List tici's saved sessions, substring-filtering on `filter` when given.
+/// `quiet` prints bare session names only, suitable as a completion source
+/// (`tici list -q <word>`).
+pub fn list_saved_sessions(save_dir: &Path, filter: Option<&str>, quiet: bool) -> Result<()> {
+    if !save_dir.exists() {
+        if !quiet {
+            println!("No saved sessions found in {}", save_dir.display());
+        }
+        return Ok(());
+    }
+
+    let mut entries: Vec<(String, Option<String>, Option<u64>)> = fs::read_dir(save_dir)
+        .with_context(|| format!("Failed to read directory: {}", save_dir.display()))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            parse_save_filename(&file_name).map(|name| {
+                let (origin_dir, saved_at) = read_metadata(&entry.path());
+                (name, origin_dir, saved_at)
+            })
+        })
+        .collect();
+
+    entries.extend(read_all_sessions_archive(save_dir));
+    entries.retain(|(name, _, _)| filter.map_or(true, |f| name.contains(f)));
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    if entries.is_empty() {
+        if !quiet {
+            println!("No saved sessions found in {}", save_dir.display());
+        }
+        return Ok(());
+    }
+
+    for (name, origin_dir, saved_at) in entries {
+        if quiet {
+            println!("{}", name);
+            continue;
+        }
+
+        let live = tmux::session_exists(&name).unwrap_or(false);
+        println!("{}{}", name, if live { " [live]" } else { "" });
+
+        if let Some(dir) = origin_dir {
+            println!("  Directory: {}", dir);
+        }
+        if let Some(ts) = saved_at {
+            println!("  Saved: {}", format_relative(ts));
+        }
+    }
+
+    Ok(())
+}
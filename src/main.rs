@@ -2,12 +2,16 @@ use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
+mod archive;
+mod list;
 mod models;
 mod restore;
 mod save;
 mod session_info;
 mod tmux;
 
+use tmux::AttachOpts;
+
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
@@ -19,6 +23,14 @@ struct Cli {
     #[arg(short = 'n', long = "dry-run", global = true)]
     dry_run: bool,
 
+    /// Attach/switch to the session in read-only mode
+    #[arg(short = 'r', long = "read-only", global = true)]
+    read_only: bool,
+
+    /// Detach other clients already attached to the target session
+    #[arg(short = 'D', long = "detach-others", global = true)]
+    detach_others: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -26,28 +38,101 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Save the current tmux session
-    Save,
+    Save {
+        /// Save every session on the tmux server, not just the current one
+        #[arg(long = "all")]
+        all: bool,
+    },
+
+    /// Restore the tmux session for the specified directory. Errors if the
+    /// session already exists (with --all, such sessions are skipped); pass
+    /// --override to replace it instead.
+    Restore {
+        /// Replay each pane's captured scrollback and re-run its captured
+        /// command when that is a known long-running program (an editor,
+        /// pager, REPL, ssh, ...), instead of leaving it at a blank shell prompt
+        #[arg(long = "restore-processes")]
+        restore_processes: bool,
+
+        /// Restore every session saved with `tici save --all`
+        #[arg(long = "all")]
+        all: bool,
+
+        /// Replace an already-existing session's windows with the archived
+        /// version. Without this, restoring onto a session that already
+        /// exists is an error; with --all, existing sessions are skipped.
+        #[arg(long = "override")]
+        override_existing: bool,
+
+        /// Attach (or switch-client) to the session once it's restored
+        #[arg(long = "attach")]
+        attach: bool,
+    },
+
+    /// List sessions tici has saved
+    List {
+        /// Only show sessions whose name contains this substring
+        filter: Option<String>,
 
-    /// Restore the tmux session for the specified directory
-    Restore,
+        /// Print only bare session names, e.g. for use as a completion source
+        #[arg(short = 'q', long = "quiet")]
+        quiet: bool,
+    },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
-    let (save_path, session_name) = session_info::get_session_info(cli.working_dir.as_ref())?;
+    let (origin_dir, save_path, session_name) =
+        session_info::get_session_info(cli.working_dir.as_ref())?;
+    let attach_opts = AttachOpts {
+        read_only: cli.read_only,
+        detach_others: cli.detach_others,
+    };
 
     match &cli.command {
-        Some(Commands::Save) => {
-            if cli.dry_run {
-                println!("Would save session to: {}", save_path.display());
-            } else {
-                save::save_tmux_session(&save_path)?;
-            }
+        Some(Commands::Save { all: true }) => {
+            let all_sessions_path = session_info::get_all_sessions_path()?;
+            save::save_all_tmux_sessions(&all_sessions_path, &origin_dir, cli.dry_run)?;
+        }
+
+        Some(Commands::Save { all: false }) => {
+            save::save_tmux_session(&save_path, &origin_dir, &session_name, cli.dry_run)?;
+        }
+
+        Some(Commands::Restore {
+            restore_processes,
+            all: true,
+            override_existing,
+            attach,
+        }) => {
+            let all_sessions_path = session_info::get_all_sessions_path()?;
+            restore::restore_all_tmux_sessions(
+                &all_sessions_path,
+                cli.dry_run,
+                *restore_processes,
+                *override_existing,
+                if *attach { Some(&attach_opts) } else { None },
+            )?;
+        }
+
+        Some(Commands::Restore {
+            restore_processes,
+            all: false,
+            override_existing,
+            attach,
+        }) => {
+            restore::restore_tmux_session(
+                &save_path,
+                &session_name,
+                cli.dry_run,
+                *restore_processes,
+                *override_existing,
+                if *attach { Some(&attach_opts) } else { None },
+            )?;
         }
 
-        Some(Commands::Restore) => {
-            restore::restore_tmux_session(&save_path, &session_name, cli.dry_run)
-                .and(tmux::switch_to_session(&session_name))?;
+        Some(Commands::List { filter, quiet }) => {
+            list::list_saved_sessions(&session_info::get_save_dir()?, filter.as_deref(), *quiet)?;
         }
 
         None => {
@@ -56,26 +141,33 @@ fn main() -> Result<()> {
                 println!("1. Find and attach to session: {}", session_name);
                 println!("2. Or create new session{}", session_name);
                 println!("3. Then restore session from: {}\n", save_path.display());
-                restore::restore_tmux_session(&save_path, &session_name, true)?;
+                restore::restore_tmux_session(&save_path, &session_name, true, false, true, None)?;
                 return Ok(());
             }
 
             // First try to find and attach to existing session
             if tmux::session_exists(&session_name)? {
-                tmux::switch_to_session(&session_name)?;
+                tmux::switch_to_session_with_opts(&session_name, &attach_opts)?;
                 return Ok(());
             }
 
             // If no existing session, create a new one with -d (detached) option
-            tmux::new_tmux_session(&session_name, true)
-                .context(format!("Failed to create sesstion {}", session_name))?;
+            tmux::new_tmux_session(
+                &session_name,
+                tmux::NewSessionOpts {
+                    detached: true,
+                    ..Default::default()
+                },
+            )
+            .context(format!("Failed to create sesstion {}", session_name))?;
 
             // try restoring session, ignore errors (if any)
-            let _ = restore::restore_tmux_session(&save_path, &session_name, false);
+            let _ =
+                restore::restore_tmux_session(&save_path, &session_name, false, false, true, None);
 
             // switch/attach to the session, then also save it
-            tmux::switch_to_session(&session_name)?;
-            save::save_tmux_session(&save_path)?;
+            tmux::switch_to_session_with_opts(&session_name, &attach_opts)?;
+            save::save_tmux_session(&save_path, &origin_dir, &session_name, false)?;
         }
     }
 
@@ -1,4 +1,6 @@
-#[derive(Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Pane {
     pub index: u32,
     pub title: String,
@@ -6,9 +8,13 @@ pub struct Pane {
     pub active: bool,
     pub current_command: String,
     pub pid: u32,
+    /// Path to the captured scrollback buffer for this pane, relative to the
+    /// save file's sidecar directory (the save file's path with its `.tmux`
+    /// extension replaced by `.d`), if one was captured.
+    pub scrollback_file: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Window {
     pub session_name: String,
     pub index: u32,
@@ -17,3 +23,9 @@ pub struct Window {
     pub layout: String,
     pub panes: Vec<Pane>,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Session {
+    pub name: String,
+    pub windows: Vec<Window>,
+}
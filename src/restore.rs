@@ -3,43 +3,43 @@ use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
 
-use crate::models::{Pane, Window};
+use crate::archive::Archive;
+use crate::models::{Pane, Session, Window};
+use crate::tmux::{self, AttachOpts, NewSessionOpts};
 
-impl Window {
-    fn from_line(line: &str) -> Result<Self, anyhow::Error> {
-        // Format: # Window: session_name:index (name) active layout
-        let line = line.trim_start_matches("# Window: ");
-        let parts: Vec<&str> = line.split('|').collect();
-        if parts.len() < 5 {
-            anyhow::bail!("Failed to read window info from: {}", line);
-        }
-
-        let (session_name, index, name, active, layout) =
-            (parts[0], parts[1], parts[2], parts[3], parts[4]);
-
-        Ok(Window {
-            session_name: session_name.to_string(),
-            index: index
-                .parse()
-                .with_context(|| format!("Failed to parse window index: {}", index))?,
-            name: name.to_string(),
-            active: active == "1",
-            layout: layout.to_string(),
-            panes: Vec::new(),
-        })
+/// Parse a saved session file into its sessions, supporting both the legacy
+/// `|`-delimited line format (detected by its `# Window: ` prefix), which
+/// only ever covered a single session, and the versioned JSON archive that
+/// replaced it.
+fn parse_sessions(content: &str) -> Result<Vec<Session>> {
+    if content.trim_start().starts_with("# Window: ") {
+        let windows = parse_legacy_windows(content)?;
+        let name = windows
+            .first()
+            .map(|w| w.session_name.clone())
+            .unwrap_or_default();
+        Ok(vec![Session { name, windows }])
+    } else {
+        Ok(Archive::from_json(content)?.sessions)
     }
 }
 
-pub fn restore_tmux_session(save_path: &PathBuf, session_name: &str, dry_run: bool) -> Result<()> {
-    // Check if file exists
-    if !save_path.exists() {
-        anyhow::bail!("No saved session found for this directory");
+/// Pick out the windows for `session_name` from a parsed archive. Plain
+/// (non `--all`) saves always contain exactly one session, so we fall back
+/// to it regardless of name when there's no ambiguity.
+fn find_session_windows(sessions: Vec<Session>, session_name: &str) -> Result<Vec<Window>> {
+    if sessions.len() == 1 {
+        return Ok(sessions.into_iter().next().unwrap().windows);
     }
 
-    // Read the saved session file
-    let content = fs::read_to_string(save_path).context("Failed to read saved session file")?;
+    sessions
+        .into_iter()
+        .find(|s| s.name == session_name)
+        .map(|s| s.windows)
+        .with_context(|| format!("No saved session named '{}' found in archive", session_name))
+}
 
-    // Parse windows and panes from the content
+fn parse_legacy_windows(content: &str) -> Result<Vec<Window>> {
     let mut windows: Vec<Window> = Vec::new();
 
     let mut lines = content.lines().peekable();
@@ -64,6 +64,8 @@ pub fn restore_tmux_session(save_path: &PathBuf, session_name: &str, dry_run: bo
 
                         let (index, active, title, path, cmd, pid) =
                             (parts[0], parts[1], parts[2], parts[3], parts[4], parts[5]);
+                        let scrollback_file =
+                            parts.get(6).filter(|s| **s != "-").map(|s| s.to_string());
                         window.panes.push(Pane {
                             index: index.parse().unwrap_or(0),
                             active: active == "1",
@@ -71,6 +73,7 @@ pub fn restore_tmux_session(save_path: &PathBuf, session_name: &str, dry_run: bo
                             current_path: path.to_string(),
                             current_command: cmd.to_string(),
                             pid: pid.parse().unwrap_or(0),
+                            scrollback_file,
                         });
                     } else {
                         lines.next(); // Skip non-pane lines
@@ -81,6 +84,53 @@ pub fn restore_tmux_session(save_path: &PathBuf, session_name: &str, dry_run: bo
         }
     }
 
+    Ok(windows)
+}
+
+impl Window {
+    fn from_line(line: &str) -> Result<Self, anyhow::Error> {
+        // Format: # Window: session_name|index|name|active|layout
+        let line = line.trim_start_matches("# Window: ");
+        let parts: Vec<&str> = line.split('|').collect();
+        if parts.len() < 5 {
+            anyhow::bail!("Failed to read window info from: {}", line);
+        }
+
+        let (session_name, index, name, active, layout) =
+            (parts[0], parts[1], parts[2], parts[3], parts[4]);
+
+        Ok(Window {
+            session_name: session_name.to_string(),
+            index: index
+                .parse()
+                .with_context(|| format!("Failed to parse window index: {}", index))?,
+            name: name.to_string(),
+            active: active == "1",
+            layout: layout.to_string(),
+            panes: Vec::new(),
+        })
+    }
+}
+
+pub fn restore_tmux_session(
+    save_path: &PathBuf,
+    session_name: &str,
+    dry_run: bool,
+    restore_processes: bool,
+    override_existing: bool,
+    attach: Option<&AttachOpts>,
+) -> Result<()> {
+    // Check if file exists
+    if !save_path.exists() {
+        anyhow::bail!("No saved session found for this directory");
+    }
+
+    // Read the saved session file
+    let content = fs::read_to_string(save_path).context("Failed to read saved session file")?;
+
+    // Parse windows and panes from the content
+    let windows = find_session_windows(parse_sessions(&content)?, session_name)?;
+
     if windows.is_empty() {
         anyhow::bail!("No windows found in saved session");
     }
@@ -90,6 +140,45 @@ pub fn restore_tmux_session(save_path: &PathBuf, session_name: &str, dry_run: bo
         return Ok(());
     }
 
+    // Sidecar directory holding captured pane scrollback, if any was saved.
+    let sidecar_dir = save_path.with_extension("d");
+
+    if tmux::session_exists(session_name)? {
+        if !override_existing {
+            anyhow::bail!(
+                "Session '{}' already exists; pass --override to replace it",
+                session_name
+            );
+        }
+    } else {
+        tmux::new_tmux_session(
+            session_name,
+            NewSessionOpts {
+                detached: true,
+                ..Default::default()
+            },
+        )
+        .with_context(|| format!("Failed to create session {}", session_name))?;
+    }
+
+    populate_session_windows(session_name, &windows, &sidecar_dir, restore_processes)?;
+
+    if let Some(opts) = attach {
+        tmux::switch_to_session_with_opts(session_name, opts)?;
+    }
+
+    Ok(())
+}
+
+/// Replace `session_name`'s windows/panes with the archived `windows`: clear
+/// everything but the first window (tmux requires at least one), recreate
+/// the rest, then select whichever window was active at save time.
+fn populate_session_windows(
+    session_name: &str,
+    windows: &[Window],
+    sidecar_dir: &std::path::Path,
+    restore_processes: bool,
+) -> Result<()> {
     // Clear all existing windows except the first one (tmux requires at least one window)
     Command::new("tmux")
         .args(["list-windows", "-t", session_name, "-F", "#{window_index}"])
@@ -109,7 +198,7 @@ pub fn restore_tmux_session(save_path: &PathBuf, session_name: &str, dry_run: bo
 
     // Create remaining windows
     for window in windows.iter() {
-        restore_window(session_name, window)?;
+        restore_window(session_name, window, sidecar_dir, restore_processes)?;
     }
 
     // Select the active window if any
@@ -127,7 +216,113 @@ pub fn restore_tmux_session(save_path: &PathBuf, session_name: &str, dry_run: bo
     Ok(())
 }
 
-fn restore_window(session_name: &str, window: &Window) -> Result<()> {
+/// Restore every session found in an archive saved with `tici save --all`,
+/// recreating the tmux server if it isn't already running. Sessions that
+/// already exist are skipped, unless `override_existing` is set, in which
+/// case their windows are replaced with the archived ones.
+pub fn restore_all_tmux_sessions(
+    save_path: &PathBuf,
+    dry_run: bool,
+    restore_processes: bool,
+    override_existing: bool,
+    attach: Option<&AttachOpts>,
+) -> Result<()> {
+    if !save_path.exists() {
+        anyhow::bail!("No saved sessions found at: {}", save_path.display());
+    }
+
+    let content = fs::read_to_string(save_path).context("Failed to read saved session file")?;
+    let sessions = parse_sessions(&content)?;
+
+    if sessions.is_empty() {
+        anyhow::bail!("No sessions found in saved archive");
+    }
+
+    if dry_run {
+        for session in &sessions {
+            print_session_info(&session.windows);
+        }
+        return Ok(());
+    }
+
+    let sidecar_dir = save_path.with_extension("d");
+
+    // Sessions actually (re)created this run, in restore order, so `--attach`
+    // has something real to target — the cwd-derived session name from
+    // `get_session_info` has no relation to the names saved in a `--all`
+    // archive.
+    let mut restored_sessions: Vec<&str> = Vec::new();
+
+    for session in &sessions {
+        if tmux::session_exists(&session.name)? {
+            if !override_existing {
+                println!("Skipping existing session: {}", session.name);
+                continue;
+            }
+            populate_session_windows(
+                &session.name,
+                &session.windows,
+                &sidecar_dir,
+                restore_processes,
+            )?;
+        } else {
+            tmux::new_tmux_session(
+                &session.name,
+                NewSessionOpts {
+                    detached: true,
+                    ..Default::default()
+                },
+            )
+            .with_context(|| format!("Failed to create session {}", session.name))?;
+
+            for window in &session.windows {
+                restore_window(&session.name, window, &sidecar_dir, restore_processes)?;
+            }
+
+            if let Some(active_window) = session.windows.iter().find(|w| w.active) {
+                Command::new("tmux")
+                    .args([
+                        "select-window",
+                        "-t",
+                        &format!("{}:{}", session.name, active_window.index),
+                    ])
+                    .output()
+                    .context("Failed to select active window")?;
+            }
+        }
+
+        println!("Restored session: {}", session.name);
+        restored_sessions.push(session.name.as_str());
+    }
+
+    if let Some(opts) = attach {
+        match restored_sessions.first() {
+            Some(session_name) => tmux::switch_to_session_with_opts(session_name, opts)?,
+            None => println!("Nothing was restored; skipping --attach"),
+        }
+    }
+
+    Ok(())
+}
+
+/// Foreground programs we'll re-issue via `send-keys` when `--restore-processes`
+/// is set. This is an allowlist, not a shell denylist: `current_command` is
+/// just the process name with no arguments, so blindly replaying *any*
+/// captured command would re-run things like `git`, `make`, or a half-typed
+/// pipeline verbatim (and without its original args). Restrict re-execution
+/// to long-running programs it's actually useful to drop back into.
+const LONG_RUNNING_PROGRAMS: &[&str] = &[
+    "vim", "nvim", "emacs", "nano", "less", "more", "man", "top", "htop", "btop", "watch", "tail",
+    "ssh", "mosh", "tmux", "screen", "python", "python3", "ipython", "node", "irb", "psql",
+    "mysql", "sqlite3",
+];
+
+fn restore_window(
+    session_name: &str,
+    window: &Window,
+    sidecar_dir: &std::path::Path,
+    restore_processes: bool,
+) -> Result<()> {
     if window.index > 0 {
         let window_format = format!("{}:{}", session_name, window.index);
         let mut args = vec!["new-window", "-t", &window_format, "-n", &window.name];
@@ -175,8 +370,24 @@ fn restore_window(session_name: &str, window: &Window) -> Result<()> {
 
     // Restore pane contents
     for pane in &window.panes {
-        // If this is the active pane, select it
         let target = format!("{}:{}.{}", session_name, window.index, pane.index);
+
+        if restore_processes {
+            if let Some(file_name) = &pane.scrollback_file {
+                restore_pane_scrollback(&target, &sidecar_dir.join(file_name)).with_context(
+                    || format!("Failed to restore scrollback for pane {}", pane.index),
+                )?;
+            }
+        }
+
+        if restore_processes && LONG_RUNNING_PROGRAMS.contains(&pane.current_command.as_str()) {
+            Command::new("tmux")
+                .args(["send-keys", "-t", &target, &pane.current_command, "Enter"])
+                .output()
+                .with_context(|| format!("Failed to re-run command in pane {}", pane.index))?;
+        }
+
+        // If this is the active pane, select it
         if pane.active {
             Command::new("tmux")
                 .args(["select-pane", "-t", &target])
@@ -188,6 +399,36 @@ fn restore_window(session_name: &str, window: &Window) -> Result<()> {
     Ok(())
 }
 
+/// Replay a captured scrollback file into `target` by running `cat` on it
+/// inside the pane, so the prior output reappears as actual pane output.
+/// We deliberately don't `paste-buffer` the raw text: that types it into the
+/// pane as keyboard input, so the shell would try to execute old command
+/// output and error messages as new commands.
+fn restore_pane_scrollback(target: &str, file: &std::path::Path) -> Result<()> {
+    if !file.exists() {
+        return Ok(());
+    }
+
+    Command::new("tmux")
+        .args([
+            "send-keys",
+            "-t",
+            target,
+            &format!("cat {}", shell_quote(file)),
+            "Enter",
+        ])
+        .output()
+        .context("Failed to replay scrollback in pane")?;
+
+    Ok(())
+}
+
+/// Single-quote `path` for use in a `send-keys` command line, escaping any
+/// embedded single quotes.
+fn shell_quote(path: &std::path::Path) -> String {
+    format!("'{}'", path.to_string_lossy().replace('\'', r"'\''"))
+}
+
 fn print_session_info(windows: &[Window]) {
     println!("Session: {}", windows[0].session_name);
     for window in windows {
@@ -1,9 +1,10 @@
 use anyhow::{Context, Result};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use crate::models::{Pane, Window};
+use crate::archive::Archive;
+use crate::models::{Pane, Session, Window};
 
 impl Window {
     fn from_format_str(format_str: &str) -> Option<Self> {
@@ -60,6 +61,7 @@ impl Window {
                     active,
                     current_command,
                     pid,
+                    scrollback_file: None,
                 });
             }
         }
@@ -68,17 +70,49 @@ impl Window {
     }
 }
 
-pub fn save_tmux_session(save_path: &PathBuf, dry_run: bool) -> Result<()> {
-    // Create the parent directory if it doesn't exist
-    if let Some(parent) = save_path.parent() {
-        fs::create_dir_all(parent)
-            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+/// Capture a pane's scrollback (including history, `-S -`) and write it to
+/// `sidecar_dir/<session>/<window>.<pane>.txt`. The session name is part of
+/// the path (not just `<window>.<pane>.txt`) because `save --all` funnels
+/// every session's panes into one sidecar directory, and window/pane indices
+/// are only unique within a session. Returns the file name (relative to
+/// `sidecar_dir`) on success, or `None` if tmux failed to capture the pane
+/// (e.g. it was already closed).
+fn capture_pane_scrollback(
+    session_name: &str,
+    window_index: u32,
+    pane: &Pane,
+    sidecar_dir: &std::path::Path,
+) -> Option<String> {
+    let target = format!("{}:{}.{}", session_name, window_index, pane.index);
+
+    let output = Command::new("tmux")
+        .args(["capture-pane", "-t", &target, "-p", "-e", "-S", "-"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
     }
 
-    // Save the current tmux session with the specified format
+    fs::create_dir_all(sidecar_dir.join(session_name)).ok()?;
+
+    let file_name = format!("{}/{}.{}.txt", session_name, window_index, pane.index);
+    fs::write(sidecar_dir.join(&file_name), output.stdout).ok()?;
+
+    Some(file_name)
+}
+
+/// Run `tmux list-windows` (with `extra_args` inserted before `-F`, e.g.
+/// `["-a"]` to span every session) and build up the `Window`/`Pane` tree,
+/// capturing scrollback for each pane along the way (except on a dry run).
+fn collect_windows(extra_args: &[&str], sidecar_dir: &Path, dry_run: bool) -> Result<Vec<Window>> {
     let format = "window\t#{session_name}\t#{window_index}\t:#{window_name}\t#{window_active}\t#{window_layout}";
+    let mut args = vec!["list-windows"];
+    args.extend_from_slice(extra_args);
+    args.extend(["-F", format]);
+
     let output = Command::new("tmux")
-        .args(["list-windows", "-F", format])
+        .args(&args)
         .output()
         .context("Failed to execute tmux list-windows")?;
 
@@ -88,47 +122,102 @@ pub fn save_tmux_session(save_path: &PathBuf, dry_run: bool) -> Result<()> {
 
     let session_info = String::from_utf8(output.stdout).context("Failed to parse tmux output")?;
 
-    // Save the session layout to file
-    let mut content = String::new();
+    let mut windows: Vec<Window> = Vec::new();
 
     for line in session_info.lines() {
         let mut window = Window::from_format_str(line)
             .with_context(|| format!("Failed to parse window format: {}", line))?;
 
-        content.push_str(&format!(
-            "# Window: {}|{}|{}|{}|{}\n",
-            window.session_name,
-            window.index,
-            window.name,
-            if window.active { "1" } else { "0" },
-            window.layout
-        ));
-
         window.get_panes()?;
 
-        for pane in &window.panes {
-            content.push_str(&format!(
-                "# Pane: {}|{}|{}|{}|{}|{}\n",
-                pane.index,
-                if pane.active { "1" } else { "0" },
-                pane.title,
-                pane.current_path,
-                pane.current_command,
-                pane.pid,
-            ));
+        if !dry_run {
+            let session_name = window.session_name.clone();
+            let window_index = window.index;
+            for pane in &mut window.panes {
+                pane.scrollback_file =
+                    capture_pane_scrollback(&session_name, window_index, pane, sidecar_dir);
+            }
+        }
+
+        windows.push(window);
+    }
+
+    Ok(windows)
+}
+
+/// Group a flat list of windows (as returned by `list-windows -a`) back into
+/// one `Session` per distinct `session_name`, preserving encounter order.
+fn group_by_session(windows: Vec<Window>) -> Vec<Session> {
+    let mut sessions: Vec<Session> = Vec::new();
+
+    for window in windows {
+        match sessions.iter_mut().find(|s| s.name == window.session_name) {
+            Some(session) => session.windows.push(window),
+            None => sessions.push(Session {
+                name: window.session_name.clone(),
+                windows: vec![window],
+            }),
         }
     }
 
+    sessions
+}
+
+fn write_archive(save_path: &PathBuf, archive: &Archive, dry_run: bool) -> Result<()> {
+    let content = archive.to_json()?;
+
     if dry_run {
         println!("Would save session to: {}", save_path.display());
         println!("---");
         println!("{}", content);
         println!("---");
     } else {
-        fs::write(&save_path, content)
+        fs::write(save_path, content)
             .with_context(|| format!("Failed to write to file: {}", save_path.display()))?;
 
         println!("Session saved to: {}", save_path.display());
     }
+
     Ok(())
 }
+
+fn prepare_save_dirs(save_path: &Path, dry_run: bool) -> Result<PathBuf> {
+    if let Some(parent) = save_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    // Sidecar directory holding captured pane scrollback, e.g.
+    // ~/.tmux/tici/session_<hash>_<name>.d/<session>/<window>.<pane>.txt
+    let sidecar_dir = save_path.with_extension("d");
+    if !dry_run {
+        fs::create_dir_all(&sidecar_dir)
+            .with_context(|| format!("Failed to create directory: {}", sidecar_dir.display()))?;
+    }
+
+    Ok(sidecar_dir)
+}
+
+pub fn save_tmux_session(
+    save_path: &PathBuf,
+    origin_dir: &Path,
+    session_name: &str,
+    dry_run: bool,
+) -> Result<()> {
+    let sidecar_dir = prepare_save_dirs(save_path, dry_run)?;
+    let windows = collect_windows(&[], &sidecar_dir, dry_run)?;
+    let archive = Archive::single(session_name, windows, origin_dir);
+    write_archive(save_path, &archive, dry_run)
+}
+
+/// Save every tmux session on the server into one archive (`tici save --all`).
+pub fn save_all_tmux_sessions(save_path: &PathBuf, origin_dir: &Path, dry_run: bool) -> Result<()> {
+    let sidecar_dir = prepare_save_dirs(save_path, dry_run)?;
+    let windows = collect_windows(&["-a"], &sidecar_dir, dry_run)?;
+    let sessions = group_by_session(windows);
+
+    println!("Found {} session(s) to save", sessions.len());
+
+    let archive = Archive::new(sessions, origin_dir);
+    write_archive(save_path, &archive, dry_run)
+}
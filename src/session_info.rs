@@ -42,9 +42,20 @@ pub fn get_session_info(working_dir: Option<&PathBuf>) -> Result<(PathBuf, PathB
         .to_string();
     let filename = format!("session_{}_{}.tmux", hash, session_name);
 
-    let home_dir = env::var("HOME").context("Failed to get HOME directory")?;
-    let save_dir = PathBuf::from(home_dir).join(".tmux").join("tici");
-    let save_path = save_dir.join(&filename);
+    let save_path = get_save_dir()?.join(&filename);
 
     Ok((dir, save_path, session_name))
 }
+
+/// Directory all tici saves (and their sidecar scrollback folders) live in:
+/// `~/.tmux/tici`.
+pub fn get_save_dir() -> Result<PathBuf> {
+    let home_dir = env::var("HOME").context("Failed to get HOME directory")?;
+    Ok(PathBuf::from(home_dir).join(".tmux").join("tici"))
+}
+
+/// Path to the single archive covering every session, used by
+/// `tici save --all` / `tici restore --all`.
+pub fn get_all_sessions_path() -> Result<PathBuf> {
+    Ok(get_save_dir()?.join("all-sessions.tmux"))
+}
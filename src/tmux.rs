@@ -25,14 +25,33 @@ pub fn session_exists(session_name: &str) -> Result<bool> {
     Ok(output.status.success())
 }
 
-pub fn switch_to_session(session_name: &str) -> Result<()> {
-    let args = if is_inside_tmux() {
-        ["switch-client", "-t", session_name]
+/// Options for attaching/switching to a session, paralleling `NewSessionOpts`.
+#[derive(Default)]
+pub struct AttachOpts {
+    /// Pass `-r`: attach/switch in read-only mode.
+    pub read_only: bool,
+    /// Pass `-d`: detach other clients already attached to the session.
+    /// Only meaningful for `attach-session` (outside tmux) — `switch-client`
+    /// has no equivalent flag, so this is ignored when inside `$TMUX`.
+    pub detach_others: bool,
+}
+
+pub fn switch_to_session_with_opts(session_name: &str, opts: &AttachOpts) -> Result<()> {
+    let mut args = if is_inside_tmux() {
+        vec!["switch-client", "-t", session_name]
     } else {
-        ["attach-session", "-t", session_name]
+        vec!["attach-session", "-t", session_name]
     };
 
-    let mut child = Command::new("tmux").args(args).spawn()?;
+    if opts.read_only {
+        args.push("-r");
+    }
+
+    if opts.detach_others && !is_inside_tmux() {
+        args.push("-d");
+    }
+
+    let mut child = Command::new("tmux").args(&args).spawn()?;
 
     let status = child.wait()?;
     if !status.success() {